@@ -0,0 +1 @@
+pub mod p2p;
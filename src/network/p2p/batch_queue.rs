@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use libp2p::gossipsub::IdentTopic;
+
+/// Number of announcements buffered per topic before a batch flushes early.
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+/// Cadence a batch is flushed on even if it hasn't filled up.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Accumulates outgoing gossip payloads per topic and hands back the batch
+/// to flush once the queue reaches `max_size`; the caller is responsible for
+/// flushing whatever remains on a timer.
+pub struct BatchQueue {
+    max_size: usize,
+    pending: HashMap<IdentTopic, Vec<Vec<u8>>>,
+}
+
+impl BatchQueue {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Enqueues `data` under `topic`, returning the items to flush
+    /// immediately if the queue just reached `max_size`.
+    pub fn enqueue(&mut self, topic: IdentTopic, data: Vec<u8>) -> Option<Vec<Vec<u8>>> {
+        let queue = self.pending.entry(topic.clone()).or_default();
+        queue.push(data);
+
+        if queue.len() >= self.max_size {
+            self.pending.remove(&topic)
+        } else {
+            None
+        }
+    }
+
+    /// Drains every non-empty queue, for the periodic flush timer.
+    pub fn drain(&mut self) -> Vec<(IdentTopic, Vec<Vec<u8>>)> {
+        self.pending
+            .drain()
+            .filter(|(_, items)| !items.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enqueue_returns_none_until_max_size_reached() {
+        let mut queue = BatchQueue::new(2);
+        let topic = IdentTopic::new("test-topic");
+
+        assert!(queue.enqueue(topic.clone(), vec![1]).is_none());
+        assert_eq!(
+            queue.enqueue(topic.clone(), vec![2]),
+            Some(vec![vec![1], vec![2]])
+        );
+    }
+
+    #[test]
+    fn enqueue_starts_a_fresh_batch_after_flushing() {
+        let mut queue = BatchQueue::new(1);
+        let topic = IdentTopic::new("test-topic");
+
+        assert_eq!(queue.enqueue(topic.clone(), vec![1]), Some(vec![vec![1]]));
+        assert_eq!(queue.enqueue(topic.clone(), vec![2]), Some(vec![vec![2]]));
+    }
+
+    #[test]
+    fn enqueue_tracks_topics_independently() {
+        let mut queue = BatchQueue::new(2);
+        let topic_a = IdentTopic::new("topic-a");
+        let topic_b = IdentTopic::new("topic-b");
+
+        assert!(queue.enqueue(topic_a, vec![1]).is_none());
+        assert!(queue.enqueue(topic_b, vec![2]).is_none());
+    }
+
+    #[test]
+    fn drain_returns_only_non_empty_queues_and_clears_them() {
+        let mut queue = BatchQueue::new(10);
+        let topic = IdentTopic::new("test-topic");
+
+        assert!(queue.enqueue(topic.clone(), vec![1]).is_none());
+
+        let drained = queue.drain();
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0], (topic, vec![vec![1]]));
+
+        assert!(queue.drain().is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use libp2p::request_response;
+use libp2p::StreamProtocol;
+use prost::Message;
+
+use crate::common::protobufs::generated::{ContactInfoRequest, SyncStatusResponse};
+
+/// Refuses to buffer an unbounded amount of data from a misbehaving peer.
+const MAX_MESSAGE_SIZE: u64 = 1024 * 1024;
+
+pub const CONTACT_INFO_PROTOCOL: &str = "/teleport/contact-info/1.0.0";
+
+#[derive(Clone, Default)]
+pub struct ContactInfoCodec;
+
+#[async_trait]
+impl request_response::Codec for ContactInfoCodec {
+    type Protocol = StreamProtocol;
+    type Request = ContactInfoRequest;
+    type Response = SyncStatusResponse;
+
+    async fn read_request<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> std::io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = Vec::new();
+        io.take(MAX_MESSAGE_SIZE).read_to_end(&mut buf).await?;
+        ContactInfoRequest::decode(buf.as_slice())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    async fn read_response<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+    ) -> std::io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = Vec::new();
+        io.take(MAX_MESSAGE_SIZE).read_to_end(&mut buf).await?;
+        SyncStatusResponse::decode(buf.as_slice())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+        request: Self::Request,
+    ) -> std::io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&request.encode_to_vec()).await?;
+        io.close().await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _protocol: &Self::Protocol,
+        io: &mut T,
+        response: Self::Response,
+    ) -> std::io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.write_all(&response.encode_to_vec()).await?;
+        io.close().await
+    }
+}
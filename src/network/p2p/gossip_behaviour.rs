@@ -0,0 +1,92 @@
+use libp2p::gossipsub::{self, PeerScoreParams, PeerScoreThresholds, ValidationMode};
+use libp2p::identity::Keypair;
+use libp2p::request_response;
+use libp2p::swarm::behaviour::toggle::Toggle;
+use libp2p::swarm::NetworkBehaviour;
+use libp2p::{mdns, PeerId, StreamProtocol};
+
+use crate::common::protobufs::generated::{ContactInfoRequest, SyncStatusResponse};
+
+use super::contact_info_codec::{ContactInfoCodec, CONTACT_INFO_PROTOCOL};
+
+#[derive(NetworkBehaviour)]
+#[behaviour(to_swarm = "GossipBehaviourEvent")]
+pub struct GossipBehaviour {
+    pub gossipsub: gossipsub::Behaviour,
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
+    pub contact_info: request_response::Behaviour<ContactInfoCodec>,
+}
+
+impl GossipBehaviour {
+    /// Builds the gossipsub behaviour with strict message signing/validation
+    /// and peer scoring enabled, so repeatedly-invalid peers get graylisted
+    /// by gossipsub itself rather than trusted forever. `enable_mdns` gates
+    /// local-network peer discovery -- on by default for local/dev setups,
+    /// expected to be turned off in production deployments. The contact-info
+    /// request-response protocol lets a peer ask a freshly-dialed peer for
+    /// its sync state right after discovery connects to it.
+    pub fn new(keypair: &Keypair, local_peer_id: PeerId, enable_mdns: bool) -> Result<Self, String> {
+        let config = gossipsub::ConfigBuilder::default()
+            .validation_mode(ValidationMode::Strict)
+            .validate_messages()
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        let mut gossipsub = gossipsub::Behaviour::new(
+            gossipsub::MessageAuthenticity::Signed(keypair.clone()),
+            config,
+        )?;
+
+        gossipsub
+            .with_peer_score(PeerScoreParams::default(), PeerScoreThresholds::default())
+            .map_err(|err| err.to_string())?;
+
+        let mdns = if enable_mdns {
+            Some(
+                mdns::tokio::Behaviour::new(mdns::Config::default(), local_peer_id)
+                    .map_err(|err| err.to_string())?,
+            )
+        } else {
+            None
+        };
+
+        let contact_info = request_response::Behaviour::new(
+            [(
+                StreamProtocol::new(CONTACT_INFO_PROTOCOL),
+                request_response::ProtocolSupport::Full,
+            )],
+            request_response::Config::default(),
+        );
+
+        Ok(Self {
+            gossipsub,
+            mdns: mdns.into(),
+            contact_info,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum GossipBehaviourEvent {
+    Gossipsub(gossipsub::Event),
+    Mdns(mdns::Event),
+    ContactInfo(request_response::Event<ContactInfoRequest, SyncStatusResponse>),
+}
+
+impl From<gossipsub::Event> for GossipBehaviourEvent {
+    fn from(event: gossipsub::Event) -> Self {
+        GossipBehaviourEvent::Gossipsub(event)
+    }
+}
+
+impl From<mdns::Event> for GossipBehaviourEvent {
+    fn from(event: mdns::Event) -> Self {
+        GossipBehaviourEvent::Mdns(event)
+    }
+}
+
+impl From<request_response::Event<ContactInfoRequest, SyncStatusResponse>> for GossipBehaviourEvent {
+    fn from(event: request_response::Event<ContactInfoRequest, SyncStatusResponse>) -> Self {
+        GossipBehaviourEvent::ContactInfo(event)
+    }
+}
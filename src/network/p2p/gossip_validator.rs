@@ -0,0 +1,13 @@
+use libp2p::gossipsub::{Message, MessageAcceptance};
+use libp2p::PeerId;
+
+/// Topic-scoped content validation for gossipsub messages. `NetworkWorker` is
+/// topic-agnostic, so format-specific checks (e.g. `pubsub_peer_discovery`'s
+/// bare `Peer` record) live behind this trait instead of being
+/// hardcoded into the worker. Implementors return `None` for any message
+/// whose topic they don't own, so other topics fall through to the default
+/// verdict rather than being rejected for not matching a format they were
+/// never meant to match.
+pub trait GossipValidator: Send + Sync {
+    fn validate(&self, propagation_source: PeerId, message: &Message) -> Option<MessageAcceptance>;
+}
@@ -0,0 +1,17 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use libp2p::swarm::SwarmEvent;
+
+use super::gossip_behaviour::GossipBehaviourEvent;
+
+/// Implemented by subsystems that want to react to events coming off the
+/// swarm. Handlers are registered with a `NetworkWorker`, which is the only
+/// thing that ever owns the swarm, so `handle` only ever sees a shared
+/// reference.
+pub trait SwarmEventHandler: Send + Sync {
+    fn handle<'a>(
+        &'a self,
+        event: &'a SwarmEvent<GossipBehaviourEvent, std::io::Error>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use libp2p::futures::FutureExt;
+use libp2p::mdns;
+use libp2p::swarm::SwarmEvent;
+use libp2p::{Multiaddr, PeerId};
+
+use super::gossip_behaviour::GossipBehaviourEvent;
+use super::handle_swarm_event::SwarmEventHandler;
+use super::network_handle::NetworkHandle;
+
+/// Discovers peers on the local network via mDNS, complementing
+/// `PubSubPeerDiscovery` which needs a reachable gossip mesh before it can
+/// find anyone. Useful on a fresh LAN or in tests where no bootstrap peer is
+/// reachable yet.
+pub struct MdnsPeerDiscovery {
+    network: NetworkHandle,
+}
+
+impl MdnsPeerDiscovery {
+    pub fn new(network: NetworkHandle) -> Self {
+        Self { network }
+    }
+}
+
+impl SwarmEventHandler for MdnsPeerDiscovery {
+    fn handle<'a>(
+        &'a self,
+        event: &'a SwarmEvent<GossipBehaviourEvent, std::io::Error>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        async move {
+            let SwarmEvent::Behaviour(GossipBehaviourEvent::Mdns(event)) = event else {
+                return;
+            };
+
+            match event {
+                mdns::Event::Discovered(discovered) => {
+                    // Group every advertised address by peer so each peer is
+                    // dialed once with all its candidates (DialOpts semantics),
+                    // not once per address.
+                    let mut addrs_by_peer: HashMap<PeerId, Vec<Multiaddr>> = HashMap::new();
+                    for (peer_id, addr) in discovered {
+                        addrs_by_peer.entry(*peer_id).or_default().push(addr.clone());
+                    }
+
+                    for (peer_id, addrs) in addrs_by_peer {
+                        println!("mDNS discovered peer {:?} at {:?}", peer_id, addrs);
+                        self.network.add_explicit_peer(peer_id).await;
+                        self.network.dial_peer(peer_id, addrs).await;
+                    }
+                }
+                mdns::Event::Expired(expired) => {
+                    for (peer_id, _addr) in expired {
+                        self.network.forget_peer(*peer_id).await;
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+}
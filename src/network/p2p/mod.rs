@@ -0,0 +1,10 @@
+pub mod batch_queue;
+pub mod contact_info_codec;
+pub mod gossip_behaviour;
+pub mod gossip_validator;
+pub mod handle_swarm_event;
+pub mod mdns_peer_discovery;
+pub mod network_handle;
+pub mod network_worker;
+pub mod peer_manager;
+pub mod pubsub_peer_discovery;
@@ -0,0 +1,299 @@
+use std::sync::Arc;
+
+use libp2p::{gossipsub::IdentTopic, Multiaddr, PeerId};
+use tokio::sync::{mpsc, oneshot, RwLock};
+
+use crate::common::protobufs::generated::{ContactInfoRequest, SyncStatusResponse};
+
+use super::peer_manager::PeerManager;
+
+const COMMAND_CHANNEL_SIZE: usize = 256;
+
+pub enum Command {
+    Dial(Multiaddr),
+    DialPeer(PeerId, Vec<Multiaddr>),
+    Publish { topic: IdentTopic, data: Vec<u8> },
+    PublishBatched { topic: IdentTopic, data: Vec<u8> },
+    Subscribe(IdentTopic),
+    Unsubscribe(IdentTopic),
+    Connected(PeerId, oneshot::Sender<bool>),
+    AddExplicitPeer(PeerId),
+    ForgetPeer(PeerId),
+    SendContactInfoRequest {
+        peer_id: PeerId,
+        request: ContactInfoRequest,
+        respond_to: oneshot::Sender<Option<SyncStatusResponse>>,
+    },
+    UpdateSyncStatus(SyncStatusResponse),
+}
+
+pub(super) fn channel() -> (mpsc::Sender<Command>, mpsc::Receiver<Command>) {
+    mpsc::channel(COMMAND_CHANNEL_SIZE)
+}
+
+/// Cheap, cloneable handle to a `NetworkWorker` running elsewhere. Every
+/// operation is dispatched as a `Command` over an mpsc channel so the
+/// `Swarm` is only ever touched by the worker that owns it.
+#[derive(Clone)]
+pub struct NetworkHandle {
+    command_tx: mpsc::Sender<Command>,
+    local_addresses: Arc<RwLock<Vec<Multiaddr>>>,
+    peer_manager: Arc<RwLock<PeerManager>>,
+}
+
+impl NetworkHandle {
+    pub(super) fn new(
+        command_tx: mpsc::Sender<Command>,
+        local_addresses: Arc<RwLock<Vec<Multiaddr>>>,
+        peer_manager: Arc<RwLock<PeerManager>>,
+    ) -> Self {
+        Self {
+            command_tx,
+            local_addresses,
+            peer_manager,
+        }
+    }
+
+    pub async fn dial(&self, addr: Multiaddr) {
+        let _ = self.command_tx.send(Command::Dial(addr)).await;
+    }
+
+    /// Dials `peer_id` via `DialOpts::peer_id(..).addresses(addrs)`: one
+    /// pinned dial across every candidate address, stopping at the first
+    /// that connects, instead of one untargeted dial per address.
+    pub async fn dial_peer(&self, peer_id: PeerId, addrs: Vec<Multiaddr>) {
+        let _ = self
+            .command_tx
+            .send(Command::DialPeer(peer_id, addrs))
+            .await;
+    }
+
+    pub async fn publish(&self, topic: IdentTopic, data: Vec<u8>) {
+        let _ = self
+            .command_tx
+            .send(Command::Publish { topic, data })
+            .await;
+    }
+
+    /// Enqueues `data` to be published under `topic` as part of the next
+    /// `Batch` envelope flush, instead of publishing it on its own.
+    pub async fn publish_batched(&self, topic: IdentTopic, data: Vec<u8>) {
+        let _ = self
+            .command_tx
+            .send(Command::PublishBatched { topic, data })
+            .await;
+    }
+
+    pub async fn subscribe(&self, topic: IdentTopic) {
+        let _ = self.command_tx.send(Command::Subscribe(topic)).await;
+    }
+
+    pub async fn unsubscribe(&self, topic: IdentTopic) {
+        let _ = self.command_tx.send(Command::Unsubscribe(topic)).await;
+    }
+
+    pub async fn is_connected(&self, peer_id: PeerId) -> bool {
+        let (tx, rx) = oneshot::channel();
+
+        if self
+            .command_tx
+            .send(Command::Connected(peer_id, tx))
+            .await
+            .is_err()
+        {
+            return false;
+        }
+
+        rx.await.unwrap_or(false)
+    }
+
+    /// Listen and external addresses the worker has observed for the local
+    /// swarm, kept up to date as `SwarmEvent`s come in.
+    pub async fn local_addresses(&self) -> Vec<Multiaddr> {
+        self.local_addresses.read().await.clone()
+    }
+
+    /// Whether gossipsub has graylisted this peer, so callers like peer
+    /// discovery can skip dialing it.
+    pub async fn is_known_bad(&self, peer_id: PeerId) -> bool {
+        self.peer_manager.read().await.is_known_bad(&peer_id)
+    }
+
+    /// Keeps `peer_id` in the gossip mesh regardless of scoring, for peers a
+    /// local discovery mechanism (e.g. mDNS) already trusts.
+    pub async fn add_explicit_peer(&self, peer_id: PeerId) {
+        let _ = self.command_tx.send(Command::AddExplicitPeer(peer_id)).await;
+    }
+
+    /// Undoes `add_explicit_peer` once a locally-discovered peer is no
+    /// longer advertised (e.g. its mDNS record expired).
+    pub async fn forget_peer(&self, peer_id: PeerId) {
+        let _ = self.command_tx.send(Command::ForgetPeer(peer_id)).await;
+    }
+
+    /// Sends a `ContactInfoRequest` to `peer_id` and waits for its
+    /// `SyncStatusResponse`, mirroring the request/response +
+    /// response-channel pattern used elsewhere in libp2p hubs. Returns
+    /// `None` if the request fails or the peer never answers.
+    pub async fn send_contact_info_request(
+        &self,
+        peer_id: PeerId,
+        request: ContactInfoRequest,
+    ) -> Option<SyncStatusResponse> {
+        let (respond_to, rx) = oneshot::channel();
+
+        self.command_tx
+            .send(Command::SendContactInfoRequest {
+                peer_id,
+                request,
+                respond_to,
+            })
+            .await
+            .ok()?;
+
+        rx.await.ok().flatten()
+    }
+
+    /// Updates the sync status the worker answers `ContactInfoRequest`s with,
+    /// so it reflects the node's current snapshot/sync-trie roots rather than
+    /// whatever was current when the worker was constructed.
+    pub async fn update_sync_status(&self, sync_status: SyncStatusResponse) {
+        let _ = self
+            .command_tx
+            .send(Command::UpdateSyncStatus(sync_status))
+            .await;
+    }
+
+    /// Records the sync roots a peer reported over the contact-info
+    /// protocol, for the sync subsystem to diff against later.
+    pub async fn note_sync_status(&self, peer_id: PeerId, sync_status: SyncStatusResponse) {
+        self.peer_manager
+            .write()
+            .await
+            .set_sync_status(peer_id, sync_status);
+    }
+
+    pub async fn sync_status(&self, peer_id: PeerId) -> Option<SyncStatusResponse> {
+        self.peer_manager.read().await.sync_status(&peer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use libp2p::identity::Keypair;
+
+    use super::*;
+
+    fn handle() -> (NetworkHandle, mpsc::Receiver<Command>) {
+        let (command_tx, command_rx) = channel();
+        let handle = NetworkHandle::new(
+            command_tx,
+            Arc::new(RwLock::new(Vec::new())),
+            Arc::new(RwLock::new(PeerManager::new())),
+        );
+        (handle, command_rx)
+    }
+
+    fn sync_status() -> SyncStatusResponse {
+        SyncStatusResponse {
+            network_id: "test-network".to_string(),
+            snapshot_root: vec![1, 2, 3],
+            sync_trie_root: vec![4, 5, 6],
+        }
+    }
+
+    /// Every `NetworkHandle` method that isn't pure local state should be
+    /// reachable purely through the command channel -- this is the claim
+    /// chunk0-2's refactor makes ("testable without a live swarm"), so
+    /// assert on the `Command` each call actually produces instead of
+    /// needing a real `Swarm` to observe its effect.
+    #[tokio::test]
+    async fn dial_peer_sends_pinned_dial_command() {
+        let (handle, mut command_rx) = handle();
+        let peer_id = Keypair::generate_ed25519().public().to_peer_id();
+        let addrs = vec!["/ip4/127.0.0.1/tcp/4001".parse().unwrap()];
+
+        handle.dial_peer(peer_id, addrs.clone()).await;
+
+        match command_rx.recv().await {
+            Some(Command::DialPeer(got_peer_id, got_addrs)) => {
+                assert_eq!(got_peer_id, peer_id);
+                assert_eq!(got_addrs, addrs);
+            }
+            other => panic!("expected Command::DialPeer, got {:?}", other.is_some()),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_sync_status_sends_command() {
+        let (handle, mut command_rx) = handle();
+
+        handle.update_sync_status(sync_status()).await;
+
+        match command_rx.recv().await {
+            Some(Command::UpdateSyncStatus(status)) => assert_eq!(status, sync_status()),
+            other => panic!("expected Command::UpdateSyncStatus, got {:?}", other.is_some()),
+        }
+    }
+
+    /// Drives the `Connected`/`SendContactInfoRequest` oneshot round-trip
+    /// with a minimal fake responder standing in for `NetworkWorker`,
+    /// mirroring the real dispatch loop without constructing a `Swarm`.
+    #[tokio::test]
+    async fn connected_and_contact_info_round_trip_through_a_fake_worker() {
+        let (handle, mut command_rx) = handle();
+        let peer_id = Keypair::generate_ed25519().public().to_peer_id();
+
+        let worker = tokio::spawn(async move {
+            let mut responses: HashMap<PeerId, SyncStatusResponse> = HashMap::new();
+            responses.insert(peer_id, sync_status());
+
+            while let Some(command) = command_rx.recv().await {
+                match command {
+                    Command::Connected(peer_id, reply) => {
+                        let _ = reply.send(responses.contains_key(&peer_id));
+                    }
+                    Command::SendContactInfoRequest {
+                        peer_id,
+                        respond_to,
+                        ..
+                    } => {
+                        let _ = respond_to.send(responses.get(&peer_id).cloned());
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        assert!(handle.is_connected(peer_id).await);
+
+        let response = handle
+            .send_contact_info_request(
+                peer_id,
+                ContactInfoRequest {
+                    network_id: "test-network".to_string(),
+                    gossip_addrs: vec![],
+                    rpc_addrs: vec![],
+                },
+            )
+            .await;
+        assert_eq!(response, Some(sync_status()));
+
+        drop(handle);
+        worker.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_addresses_and_sync_status_are_read_directly_without_the_channel() {
+        let (handle, _command_rx) = handle();
+        let peer_id = Keypair::generate_ed25519().public().to_peer_id();
+
+        assert!(handle.local_addresses().await.is_empty());
+        assert!(handle.sync_status(peer_id).await.is_none());
+
+        handle.note_sync_status(peer_id, sync_status()).await;
+        assert_eq!(handle.sync_status(peer_id).await, Some(sync_status()));
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use libp2p::futures::StreamExt;
+use libp2p::gossipsub::{
+    Event as GossipsubEvent, IdentTopic, Message, MessageAcceptance, MessageId,
+};
+use libp2p::request_response::{self, OutboundRequestId};
+use libp2p::swarm::dial_opts::DialOpts;
+use libp2p::swarm::SwarmEvent;
+use libp2p::{Multiaddr, PeerId, Swarm};
+use prost::Message as _;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::time::{self, Interval};
+
+use crate::common::protobufs::generated::{Batch, ContactInfoRequest, SyncStatusResponse};
+
+use super::batch_queue::{BatchQueue, DEFAULT_BATCH_SIZE, DEFAULT_FLUSH_INTERVAL};
+use super::gossip_behaviour::{GossipBehaviour, GossipBehaviourEvent};
+use super::gossip_validator::GossipValidator;
+use super::handle_swarm_event::SwarmEventHandler;
+use super::network_handle::{channel, Command, NetworkHandle};
+use super::peer_manager::PeerManager;
+
+/// Number of recent message ids kept for gossip dedup before the oldest are
+/// evicted; bounds `NetworkWorker`'s memory instead of remembering every
+/// message ever seen for the node's lifetime.
+const SEEN_MESSAGE_CACHE_SIZE: usize = 4096;
+
+/// Exclusively owns the `Swarm` and drives it from a single `tokio::select!`
+/// loop, fanning `SwarmEvent`s out to registered handlers and servicing
+/// `Command`s issued by cloned `NetworkHandle`s. This replaces locking the
+/// swarm from every call site with a single-owner event loop.
+pub struct NetworkWorker {
+    swarm: Swarm<GossipBehaviour>,
+    command_rx: mpsc::Receiver<Command>,
+    local_addresses: Arc<RwLock<Vec<Multiaddr>>>,
+    peer_manager: Arc<RwLock<PeerManager>>,
+    batches: BatchQueue,
+    flush_timer: Interval,
+    seen_messages: HashSet<MessageId>,
+    seen_messages_order: VecDeque<MessageId>,
+    local_sync_status: SyncStatusResponse,
+    pending_contact_info: HashMap<OutboundRequestId, oneshot::Sender<Option<SyncStatusResponse>>>,
+    handlers: Vec<Arc<dyn SwarmEventHandler + Send + Sync>>,
+    validators: Vec<Arc<dyn GossipValidator + Send + Sync>>,
+}
+
+impl NetworkWorker {
+    /// `local_sync_status` is what we hand back whenever a peer asks us for
+    /// our contact info; callers update the node's snapshot/sync-trie roots
+    /// as they change by sending `Command::UpdateSyncStatus` through the
+    /// returned `NetworkHandle`.
+    pub fn new(
+        swarm: Swarm<GossipBehaviour>,
+        local_sync_status: SyncStatusResponse,
+    ) -> (Self, NetworkHandle) {
+        let (command_tx, command_rx) = channel();
+        let local_addresses = Arc::new(RwLock::new(Vec::new()));
+        let peer_manager = Arc::new(RwLock::new(PeerManager::new()));
+
+        let worker = Self {
+            swarm,
+            command_rx,
+            local_addresses: local_addresses.clone(),
+            peer_manager: peer_manager.clone(),
+            batches: BatchQueue::new(DEFAULT_BATCH_SIZE),
+            flush_timer: time::interval(DEFAULT_FLUSH_INTERVAL),
+            seen_messages: HashSet::new(),
+            seen_messages_order: VecDeque::new(),
+            local_sync_status,
+            pending_contact_info: HashMap::new(),
+            handlers: Vec::new(),
+            validators: Vec::new(),
+        };
+
+        (
+            worker,
+            NetworkHandle::new(command_tx, local_addresses, peer_manager),
+        )
+    }
+
+    pub fn register_handler(&mut self, handler: Arc<dyn SwarmEventHandler + Send + Sync>) {
+        self.handlers.push(handler);
+    }
+
+    /// Registers a topic-scoped content validator consulted by
+    /// `acceptance_for`; see `GossipValidator` for why this isn't baked into
+    /// the worker itself.
+    pub fn register_validator(&mut self, validator: Arc<dyn GossipValidator + Send + Sync>) {
+        self.validators.push(validator);
+    }
+
+    /// Runs until every `NetworkHandle` has been dropped. Intended to be
+    /// spawned as its own task for the lifetime of the node.
+    pub async fn run(mut self) {
+        loop {
+            tokio::select! {
+                event = self.swarm.select_next_some() => {
+                    if let SwarmEvent::Behaviour(GossipBehaviourEvent::ContactInfo(event)) = event {
+                        self.handle_contact_info_event(event).await;
+                    } else {
+                        self.track_local_addresses(&event).await;
+                        let acceptance = self.validate_gossip_message(&event).await;
+                        if acceptance != Some(MessageAcceptance::Reject)
+                            && acceptance != Some(MessageAcceptance::Ignore)
+                        {
+                            self.dispatch(&event).await;
+                        }
+                    }
+                }
+                command = self.command_rx.recv() => {
+                    match command {
+                        Some(command) => self.handle_command(command),
+                        None => break,
+                    }
+                }
+                _ = self.flush_timer.tick() => {
+                    self.flush_batches();
+                }
+            }
+        }
+    }
+
+    /// Flushes every topic with pending queued announcements as a single
+    /// `Batch` envelope, regardless of whether it has filled up yet.
+    fn flush_batches(&mut self) {
+        for (topic, items) in self.batches.drain() {
+            self.publish_batch(topic, items);
+        }
+    }
+
+    fn publish_batch(&mut self, topic: IdentTopic, items: Vec<Vec<u8>>) {
+        let batch = Batch { data: items };
+
+        if let Err(err) = self
+            .swarm
+            .behaviour_mut()
+            .gossipsub
+            .publish(topic, batch.encode_to_vec())
+        {
+            println!("Failed to publish batch: {:?}", err);
+        }
+    }
+
+    async fn track_local_addresses(
+        &self,
+        event: &SwarmEvent<GossipBehaviourEvent, std::io::Error>,
+    ) {
+        match event {
+            SwarmEvent::NewListenAddr { address, .. }
+            | SwarmEvent::ExternalAddrConfirmed { address, .. } => {
+                self.local_addresses.write().await.push(address.clone());
+            }
+            SwarmEvent::ExpiredListenAddr { address, .. }
+            | SwarmEvent::ExternalAddrExpired { address, .. } => {
+                self.local_addresses.write().await.retain(|a| a != address);
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports a validation verdict back to gossipsub for every message it
+    /// hands us, so it can graylist peers that keep sending bad records, and
+    /// returns that verdict so `run` can gate `dispatch` on it -- a `Reject`
+    /// must stop the message from reaching handlers like
+    /// `PubSubPeerDiscovery`, not just affect peer scoring. `Ignore` for
+    /// duplicates and self-origin; otherwise the first registered
+    /// `GossipValidator` that recognizes the message's topic decides
+    /// `Accept`/`Reject`, defaulting to `Accept` for topics no validator
+    /// owns. Returns `None` for events that aren't a gossipsub message.
+    async fn validate_gossip_message(
+        &mut self,
+        event: &SwarmEvent<GossipBehaviourEvent, std::io::Error>,
+    ) -> Option<MessageAcceptance> {
+        let SwarmEvent::Behaviour(GossipBehaviourEvent::Gossipsub(GossipsubEvent::Message {
+            propagation_source,
+            message_id,
+            message,
+        })) = event
+        else {
+            return None;
+        };
+
+        let acceptance = self
+            .acceptance_for(*propagation_source, message_id, message)
+            .await;
+
+        if acceptance == MessageAcceptance::Accept {
+            self.peer_manager
+                .write()
+                .await
+                .record_seen(*propagation_source);
+        }
+
+        if let Some(score) = self
+            .swarm
+            .behaviour()
+            .gossipsub
+            .peer_score(propagation_source)
+        {
+            self.peer_manager
+                .write()
+                .await
+                .update_score(*propagation_source, score);
+        }
+
+        self.swarm
+            .behaviour_mut()
+            .gossipsub
+            .report_message_validation_result(message_id, propagation_source, acceptance);
+
+        Some(acceptance)
+    }
+
+    /// Records `message_id` as seen, returning `true` the first time it's
+    /// recorded and `false` on every repeat. Evicts the oldest id once the
+    /// cache exceeds `SEEN_MESSAGE_CACHE_SIZE`, so dedup tracking doesn't
+    /// grow for the lifetime of the node.
+    fn record_seen_message(&mut self, message_id: MessageId) -> bool {
+        if !self.seen_messages.insert(message_id.clone()) {
+            return false;
+        }
+
+        self.seen_messages_order.push_back(message_id);
+
+        if self.seen_messages_order.len() > SEEN_MESSAGE_CACHE_SIZE {
+            if let Some(oldest) = self.seen_messages_order.pop_front() {
+                self.seen_messages.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    async fn acceptance_for(
+        &mut self,
+        propagation_source: PeerId,
+        message_id: &MessageId,
+        message: &Message,
+    ) -> MessageAcceptance {
+        if propagation_source == *self.swarm.local_peer_id() {
+            return MessageAcceptance::Ignore;
+        }
+
+        if !self.record_seen_message(message_id.clone()) {
+            return MessageAcceptance::Ignore;
+        }
+
+        for validator in &self.validators {
+            if let Some(acceptance) = validator.validate(propagation_source, message) {
+                return acceptance;
+            }
+        }
+
+        MessageAcceptance::Accept
+    }
+
+    /// Answers inbound `ContactInfoRequest`s with our current sync status
+    /// and resolves the oneshot a `send_contact_info_request` caller is
+    /// waiting on once a response (or failure) comes back for its request.
+    async fn handle_contact_info_event(
+        &mut self,
+        event: request_response::Event<ContactInfoRequest, SyncStatusResponse>,
+    ) {
+        match event {
+            request_response::Event::Message { message, .. } => match message {
+                request_response::Message::Request { channel, .. } => {
+                    let response = self.local_sync_status.clone();
+                    let _ = self
+                        .swarm
+                        .behaviour_mut()
+                        .contact_info
+                        .send_response(channel, response);
+                }
+                request_response::Message::Response {
+                    request_id,
+                    response,
+                } => {
+                    if let Some(respond_to) = self.pending_contact_info.remove(&request_id) {
+                        let _ = respond_to.send(Some(response));
+                    }
+                }
+            },
+            request_response::Event::OutboundFailure { request_id, .. } => {
+                if let Some(respond_to) = self.pending_contact_info.remove(&request_id) {
+                    let _ = respond_to.send(None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn dispatch(&self, event: &SwarmEvent<GossipBehaviourEvent, std::io::Error>) {
+        for handler in &self.handlers {
+            handler.handle(event).await;
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) {
+        match command {
+            Command::Dial(addr) => {
+                if let Err(err) = self.swarm.dial(addr) {
+                    println!("Failed to dial: {:?}", err);
+                }
+            }
+            Command::DialPeer(peer_id, addrs) => {
+                let opts = DialOpts::peer_id(peer_id).addresses(addrs).build();
+
+                if let Err(err) = self.swarm.dial(opts) {
+                    println!("Failed to dial peer {:?}: {:?}", peer_id, err);
+                }
+            }
+            Command::Publish { topic, data } => {
+                if let Err(err) = self.swarm.behaviour_mut().gossipsub.publish(topic, data) {
+                    println!("Failed to publish: {:?}", err);
+                }
+            }
+            Command::PublishBatched { topic, data } => {
+                if let Some(items) = self.batches.enqueue(topic.clone(), data) {
+                    self.publish_batch(topic, items);
+                }
+            }
+            Command::Subscribe(topic) => {
+                if let Err(err) = self.swarm.behaviour_mut().gossipsub.subscribe(&topic) {
+                    println!("Failed to subscribe to {:?}: {:?}", topic, err);
+                }
+            }
+            Command::Unsubscribe(topic) => {
+                if let Err(err) = self.swarm.behaviour_mut().gossipsub.unsubscribe(&topic) {
+                    println!("Failed to unsubscribe from {:?}: {:?}", topic, err);
+                }
+            }
+            Command::Connected(peer_id, reply) => {
+                let _ = reply.send(self.swarm.is_connected(&peer_id));
+            }
+            Command::AddExplicitPeer(peer_id) => {
+                self.swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+            }
+            Command::ForgetPeer(peer_id) => {
+                self.swarm.behaviour_mut().gossipsub.remove_explicit_peer(&peer_id);
+            }
+            Command::SendContactInfoRequest {
+                peer_id,
+                request,
+                respond_to,
+            } => {
+                let request_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .contact_info
+                    .send_request(&peer_id, request);
+                self.pending_contact_info.insert(request_id, respond_to);
+            }
+            Command::UpdateSyncStatus(sync_status) => {
+                self.local_sync_status = sync_status;
+            }
+        }
+    }
+}
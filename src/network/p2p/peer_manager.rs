@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use libp2p::PeerId;
+
+use crate::common::protobufs::generated::SyncStatusResponse;
+
+/// Mirrors the default graylist threshold gossipsub's `PeerScoreThresholds`
+/// ships with; peers at or below this are treated as known-bad.
+const GRAYLIST_SCORE_THRESHOLD: f64 = -80.0;
+
+#[derive(Debug, Clone)]
+struct PeerRecord {
+    score: f64,
+    last_seen: Instant,
+    sync_status: Option<SyncStatusResponse>,
+}
+
+impl PeerRecord {
+    fn seen_now() -> Self {
+        Self {
+            score: 0.0,
+            last_seen: Instant::now(),
+            sync_status: None,
+        }
+    }
+}
+
+/// Lightweight per-peer bookkeeping -- gossipsub score snapshots and
+/// last-seen timestamps -- so subsystems like peer discovery can avoid
+/// dialing peers gossipsub has already graylisted.
+#[derive(Default)]
+pub struct PeerManager {
+    peers: HashMap<PeerId, PeerRecord>,
+}
+
+impl PeerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_seen(&mut self, peer_id: PeerId) {
+        self.peers
+            .entry(peer_id)
+            .and_modify(|record| record.last_seen = Instant::now())
+            .or_insert_with(PeerRecord::seen_now);
+    }
+
+    pub fn update_score(&mut self, peer_id: PeerId, score: f64) {
+        self.peers
+            .entry(peer_id)
+            .and_modify(|record| record.score = score)
+            .or_insert_with(|| PeerRecord {
+                score,
+                ..PeerRecord::seen_now()
+            });
+    }
+
+    pub fn last_seen(&self, peer_id: &PeerId) -> Option<Instant> {
+        self.peers.get(peer_id).map(|record| record.last_seen)
+    }
+
+    pub fn is_known_bad(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .get(peer_id)
+            .map(|record| record.score <= GRAYLIST_SCORE_THRESHOLD)
+            .unwrap_or(false)
+    }
+
+    /// Records the sync roots a peer reported over the contact-info
+    /// protocol, for the sync subsystem to diff against later.
+    pub fn set_sync_status(&mut self, peer_id: PeerId, sync_status: SyncStatusResponse) {
+        self.peers
+            .entry(peer_id)
+            .and_modify(|record| record.sync_status = Some(sync_status.clone()))
+            .or_insert_with(|| PeerRecord {
+                sync_status: Some(sync_status),
+                ..PeerRecord::seen_now()
+            });
+    }
+
+    pub fn sync_status(&self, peer_id: &PeerId) -> Option<SyncStatusResponse> {
+        self.peers
+            .get(peer_id)
+            .and_then(|record| record.sync_status.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync_status() -> SyncStatusResponse {
+        SyncStatusResponse {
+            network_id: "test-network".to_string(),
+            snapshot_root: vec![1, 2, 3],
+            sync_trie_root: vec![4, 5, 6],
+        }
+    }
+
+    #[test]
+    fn unknown_peer_is_not_known_bad() {
+        let manager = PeerManager::new();
+        assert!(!manager.is_known_bad(&PeerId::random()));
+    }
+
+    #[test]
+    fn peer_above_threshold_is_not_known_bad() {
+        let mut manager = PeerManager::new();
+        let peer_id = PeerId::random();
+
+        manager.update_score(peer_id, GRAYLIST_SCORE_THRESHOLD + 1.0);
+
+        assert!(!manager.is_known_bad(&peer_id));
+    }
+
+    #[test]
+    fn peer_at_or_below_threshold_is_known_bad() {
+        let mut manager = PeerManager::new();
+        let peer_id = PeerId::random();
+
+        manager.update_score(peer_id, GRAYLIST_SCORE_THRESHOLD);
+        assert!(manager.is_known_bad(&peer_id));
+
+        manager.update_score(peer_id, GRAYLIST_SCORE_THRESHOLD - 1.0);
+        assert!(manager.is_known_bad(&peer_id));
+    }
+
+    #[test]
+    fn record_seen_tracks_last_seen_without_touching_score() {
+        let mut manager = PeerManager::new();
+        let peer_id = PeerId::random();
+
+        assert!(manager.last_seen(&peer_id).is_none());
+
+        manager.record_seen(peer_id);
+
+        assert!(manager.last_seen(&peer_id).is_some());
+        assert!(!manager.is_known_bad(&peer_id));
+    }
+
+    #[test]
+    fn sync_status_round_trips_per_peer() {
+        let mut manager = PeerManager::new();
+        let peer_id = PeerId::random();
+
+        assert!(manager.sync_status(&peer_id).is_none());
+
+        manager.set_sync_status(peer_id, sync_status());
+
+        assert_eq!(manager.sync_status(&peer_id), Some(sync_status()));
+    }
+}
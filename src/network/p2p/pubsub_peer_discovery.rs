@@ -1,24 +1,33 @@
 use std::{sync::Arc, time::Duration};
 
 use libp2p::futures::{Future, FutureExt};
-use libp2p::gossipsub::Event as GossipsubEvent;
+use libp2p::gossipsub::{Event as GossipsubEvent, Message as GossipsubMessage, MessageAcceptance};
+use libp2p::identity::PublicKey;
 use libp2p::swarm::SwarmEvent;
-use libp2p::{gossipsub::IdentTopic, Swarm};
+use libp2p::{gossipsub::IdentTopic, Multiaddr, PeerId};
+use prost::Message;
 use std::pin::Pin;
 use tokio::sync::Mutex;
 use tokio::time;
 
+use crate::common::protobufs::generated::{ContactInfoRequest, Peer};
 use crate::core::errors::HubError;
 
-use super::gossip_behaviour::{GossipBehaviour, GossipBehaviourEvent};
+use super::gossip_behaviour::GossipBehaviourEvent;
+use super::gossip_validator::GossipValidator;
 use super::handle_swarm_event::SwarmEventHandler;
+use super::network_handle::NetworkHandle;
 
 pub struct PubSubPeerDiscovery {
     interval: Duration,
     listen_only: bool,
     is_started: bool,
     topic: IdentTopic,
-    swarm: Arc<Mutex<Swarm<GossipBehaviour>>>,
+    local_peer_id: PeerId,
+    local_public_key: PublicKey,
+    network_id: String,
+    rpc_addrs: Vec<Multiaddr>,
+    network: NetworkHandle,
     stop_signal: Arc<Mutex<bool>>,
 }
 
@@ -26,7 +35,10 @@ impl PubSubPeerDiscovery {
     pub fn new(
         interval: Duration,
         listen_only: bool,
-        swarm: Arc<Mutex<Swarm<GossipBehaviour>>>,
+        local_public_key: PublicKey,
+        network_id: String,
+        rpc_addrs: Vec<Multiaddr>,
+        network: NetworkHandle,
         topic: IdentTopic,
     ) -> Self {
         Self {
@@ -34,7 +46,11 @@ impl PubSubPeerDiscovery {
             listen_only,
             is_started: false,
             topic,
-            swarm,
+            local_peer_id: local_public_key.to_peer_id(),
+            local_public_key,
+            network_id,
+            rpc_addrs,
+            network,
             stop_signal: Arc::new(Mutex::new(false)),
         }
     }
@@ -48,13 +64,7 @@ impl PubSubPeerDiscovery {
             return Ok(());
         }
 
-        self.swarm
-            .lock()
-            .await
-            .behaviour_mut()
-            .gossipsub
-            .subscribe(&self.topic)
-            .unwrap();
+        self.network.subscribe(self.topic.clone()).await;
 
         self.is_started = true;
 
@@ -62,10 +72,11 @@ impl PubSubPeerDiscovery {
             return Ok(());
         }
 
-        broadcast(self.swarm.clone(), &self.topic).await;
+        broadcast(&self.network, &self.local_public_key, &self.topic).await;
 
         let stop_signal = self.stop_signal.clone();
-        let swarm = self.swarm.clone();
+        let network = self.network.clone();
+        let local_public_key = self.local_public_key.clone();
         let topic = self.topic.clone();
         let interval = self.interval;
 
@@ -79,7 +90,7 @@ impl PubSubPeerDiscovery {
                             break;
                         }
 
-                        broadcast(swarm.clone(), &topic).await;
+                        broadcast(&network, &local_public_key, &topic).await;
                     }
 
                     _ = tokio::signal::ctrl_c() => {
@@ -98,31 +109,108 @@ impl PubSubPeerDiscovery {
             return Ok(());
         }
 
-        // Unsubscribe from the topics
-        self.swarm
-            .lock()
-            .await
-            .behaviour_mut()
-            .gossipsub
-            .unsubscribe(&self.topic)
-            .unwrap();
+        self.network.unsubscribe(self.topic.clone()).await;
 
         self.is_started = false;
 
         Ok(())
     }
+
+    async fn process_peer_record(&self, data: &[u8]) {
+        let peer = match Peer::decode(data) {
+            Ok(peer) => peer,
+            Err(err) => {
+                println!("Failed to decode peer record: {:?}", err);
+                return;
+            }
+        };
+
+        let public_key = match PublicKey::try_decode_protobuf(&peer.public_key) {
+            Ok(public_key) => public_key,
+            Err(err) => {
+                println!("Failed to decode peer public key: {:?}", err);
+                return;
+            }
+        };
+
+        let peer_id = public_key.to_peer_id();
+
+        if peer_id == self.local_peer_id
+            || self.network.is_connected(peer_id).await
+            || self.network.is_known_bad(peer_id).await
+        {
+            return;
+        }
+
+        let addrs = peer
+            .addrs
+            .iter()
+            .filter_map(|addr| Multiaddr::try_from(addr.clone()).ok())
+            .collect::<Vec<_>>();
+
+        if addrs.is_empty() {
+            return;
+        }
+
+        println!("Discovered peer {:?} at {:?}", peer_id, addrs);
+
+        self.network.dial_peer(peer_id, addrs).await;
+    }
+
+    /// Asks a freshly-connected peer for its contact info, so we know
+    /// before exchanging any sync state whether it's even on our network.
+    async fn send_contact_info_request(&self, peer_id: PeerId) {
+        let gossip_addrs = self
+            .network
+            .local_addresses()
+            .await
+            .into_iter()
+            .map(|addr| addr.to_vec())
+            .collect();
+
+        let rpc_addrs = self.rpc_addrs.iter().map(|addr| addr.to_vec()).collect();
+
+        let request = ContactInfoRequest {
+            network_id: self.network_id.clone(),
+            gossip_addrs,
+            rpc_addrs,
+        };
+
+        let Some(response) = self
+            .network
+            .send_contact_info_request(peer_id, request)
+            .await
+        else {
+            return;
+        };
+
+        if response.network_id != self.network_id {
+            println!(
+                "Peer {:?} is on network {:?}, not {:?}; ignoring its sync status",
+                peer_id, response.network_id, self.network_id
+            );
+            return;
+        }
+
+        self.network.note_sync_status(peer_id, response).await;
+    }
 }
 
 impl SwarmEventHandler for PubSubPeerDiscovery {
     fn handle<'a>(
         &'a self,
         event: &'a SwarmEvent<GossipBehaviourEvent, std::io::Error>,
-    ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
         async move {
             if !self.is_started {
                 return;
             }
 
+            if let SwarmEvent::ConnectionEstablished { peer_id, .. } = event {
+                self.send_contact_info_request(*peer_id).await;
+                return;
+            }
+
             if let SwarmEvent::Behaviour(event) = event {
                 match event {
                     GossipBehaviourEvent::Gossipsub(event) => {
@@ -136,19 +224,11 @@ impl SwarmEventHandler for PubSubPeerDiscovery {
                                 return;
                             }
 
-                            let locked_swarm = self.swarm.lock().await;
-                            let local_peer_id = locked_swarm.local_peer_id();
-
-                            if local_peer_id == propagation_source {
+                            if self.local_peer_id == *propagation_source {
                                 return;
                             }
 
-                            println!(
-                                "Received message from {:?}: {:?}",
-                                propagation_source, message
-                            );
-
-                            todo!("dial peer")
+                            self.process_peer_record(&message.data).await;
                         }
                     }
                     _ => {}
@@ -159,15 +239,64 @@ impl SwarmEventHandler for PubSubPeerDiscovery {
     }
 }
 
-pub async fn broadcast(swarm: Arc<Mutex<Swarm<GossipBehaviour>>>, topic: &IdentTopic) {
-    // TODO: This is likely wrong - js-libp2p encodes using protobuf over
-    // public key and multiaddresses
-    let encoded_peer_id = swarm.lock().await.local_peer_id().to_bytes();
+impl GossipValidator for PubSubPeerDiscovery {
+    /// Verifies a discovery-topic message is a well-formed `Peer` record
+    /// attributed to the peer gossipsub says authored it. `None` for every
+    /// other topic, so `NetworkWorker` falls through to its default verdict
+    /// instead of rejecting wire formats this subsystem doesn't own.
+    ///
+    /// This is published and parsed as a bare `Peer`, not wrapped in a
+    /// `Batch` envelope: the topic is shared with real js-libp2p hubs, whose
+    /// `peer-discovery` PubSub router publishes bare `Peer` records, and
+    /// `Batch`/`Peer` are wire-ambiguous on their own (both have a single
+    /// length-delimited field 1), so this subsystem must speak the same
+    /// unwrapped format both ways to stay interoperable.
+    fn validate(
+        &self,
+        propagation_source: PeerId,
+        message: &GossipsubMessage,
+    ) -> Option<MessageAcceptance> {
+        if self.topic.to_string() != message.topic.to_string() {
+            return None;
+        }
 
-    let _ = swarm
-        .lock()
+        let source = message.source.unwrap_or(propagation_source);
+
+        let peer = match Peer::decode(message.data.as_slice()) {
+            Ok(peer) => peer,
+            Err(_) => return Some(MessageAcceptance::Reject),
+        };
+
+        let public_key = match PublicKey::try_decode_protobuf(&peer.public_key) {
+            Ok(public_key) => public_key,
+            Err(_) => return Some(MessageAcceptance::Reject),
+        };
+
+        if public_key.to_peer_id() != source {
+            return Some(MessageAcceptance::Reject);
+        }
+
+        Some(MessageAcceptance::Accept)
+    }
+}
+
+/// Publishes a bare `Peer` record on `topic`, matching the wire format a
+/// real js-libp2p hub's `peer-discovery` PubSub router publishes. Not
+/// routed through `publish_batched`: wrapping this in chunk0-4's `Batch`
+/// envelope would make it unparsable to (and by) js-libp2p peers, since
+/// `Batch` and `Peer` are wire-ambiguous length-delimited field-1 messages.
+pub async fn broadcast(network: &NetworkHandle, local_public_key: &PublicKey, topic: &IdentTopic) {
+    let addrs = network
+        .local_addresses()
         .await
-        .behaviour_mut()
-        .gossipsub
-        .publish(topic.clone(), encoded_peer_id);
+        .into_iter()
+        .map(|addr| addr.to_vec())
+        .collect::<Vec<_>>();
+
+    let peer = Peer {
+        public_key: local_public_key.encode_protobuf(),
+        addrs,
+    };
+
+    network.publish(topic.clone(), peer.encode_to_vec()).await;
 }
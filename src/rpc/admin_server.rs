@@ -1,23 +1,72 @@
-use crate::common::protobufs::generated::{admin_service_server::AdminService, *};
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
 use tonic::{Request, Response, Status};
 
-#[derive(Debug, Default)]
-pub struct AdminServer {}
+use crate::common::protobufs::generated::{admin_service_server::AdminService, *};
+use crate::storage::store::Store;
+use crate::storage::trie::sync_trie::SyncTrie;
+
+pub struct AdminServer {
+    store: Arc<Store>,
+    trie: Arc<RwLock<SyncTrie>>,
+}
+
+impl AdminServer {
+    pub fn new(store: Arc<Store>, trie: Arc<RwLock<SyncTrie>>) -> Self {
+        Self { store, trie }
+    }
+}
 
 #[tonic::async_trait]
 impl AdminService for AdminServer {
     async fn rebuild_sync_trie(
         &self,
-        request: tonic::Request<Empty>,
+        _request: Request<Empty>,
     ) -> Result<Response<Empty>, Status> {
-        todo!()
+        let messages = self
+            .store
+            .all_messages_in_commit_order()
+            .map_err(|err| Status::internal(format!("failed to read message store: {}", err)))?;
+
+        // Build the replacement trie off to the side so concurrent readers
+        // keep seeing the current one until the rebuild has fully succeeded.
+        let mut rebuilt = SyncTrie::new();
+
+        for (index, message) in messages.iter().enumerate() {
+            rebuilt.insert(&message.sync_trie_key()).map_err(|err| {
+                Status::internal(format!(
+                    "failed to rebuild sync trie at message {} of {}: {}",
+                    index,
+                    messages.len(),
+                    err
+                ))
+            })?;
+        }
+
+        *self.trie.write().await = rebuilt;
+
+        Ok(Response::new(Empty {}))
     }
+
     async fn delete_all_messages_from_db(
         &self,
-        request: Request<Empty>,
+        _request: Request<Empty>,
     ) -> Result<Response<Empty>, Status> {
-        todo!()
+        // Hold the trie lock across both steps so no reader can observe a
+        // state where the messages are gone but the trie still has entries
+        // for them, or vice versa.
+        let mut trie = self.trie.write().await;
+
+        self.store.truncate_messages().map_err(|err| {
+            Status::internal(format!("failed to truncate message store: {}", err))
+        })?;
+
+        *trie = SyncTrie::new();
+
+        Ok(Response::new(Empty {}))
     }
+
     async fn submit_on_chain_event(
         &self,
         request: Request<OnChainEvent>,